@@ -4,7 +4,8 @@ use quote::quote;
 use syn::{
     parenthesized,
     parse::{Parse, ParseStream},
-    parse_macro_input, token, Attribute, DeriveInput, GenericParam, Result,
+    parse_macro_input, token, Attribute, Data, DeriveInput, Error, Field, Fields, GenericParam,
+    Ident, Result, Type,
 };
 
 struct ParseParenthesed {
@@ -22,20 +23,102 @@ impl Parse for ParseParenthesed {
     }
 }
 
-fn get_serializer(attrs: Vec<Attribute>, default: &str) -> TokenStream2 {
+fn get_serializer(attrs: &[Attribute], default: &str) -> TokenStream2 {
     let default_token = default.parse::<TokenStream2>().unwrap();
+    get_field_serializer(attrs, &default_token)
+}
+
+/// Like [`get_serializer`], but falls back to an already-resolved serializer (e.g. the
+/// struct-level one) instead of a hardcoded default. Used to let individual fields of a
+/// `#[redis_hash]` struct override the serializer with their own `#[redis_serializer(...)]`.
+fn get_field_serializer(attrs: &[Attribute], fallback: &TokenStream2) -> TokenStream2 {
     attrs
-        .into_iter()
+        .iter()
         .find(|a| a.path.segments.len() == 1 && a.path.segments[0].ident == "redis_serializer")
         .map(|Attribute { tokens, .. }| {
-            let tokens = tokens.into();
+            let tokens = tokens.clone().into();
             let ParseParenthesed { field, .. } = parse_macro_input!(tokens as ParseParenthesed);
             field.into()
         })
-        .unwrap_or(default_token.into())
+        .unwrap_or_else(|| fallback.clone().into())
         .into()
 }
 
+/// Returns the raw tokens inside `#[name(...)]`, if that attribute is present, or a
+/// `compile_error!` token stream if it's present but malformed (e.g. missing the parens).
+fn get_attr_args(
+    attrs: &[Attribute],
+    name: &str,
+) -> std::result::Result<Option<TokenStream2>, TokenStream2> {
+    let Some(attr) = attrs
+        .iter()
+        .find(|a| a.path.segments.len() == 1 && a.path.segments[0].ident == name)
+    else {
+        return Ok(None);
+    };
+    match syn::parse2::<ParseParenthesed>(attr.tokens.clone()) {
+        Ok(ParseParenthesed { field, .. }) => Ok(Some(field)),
+        Err(e) => Err(e.to_compile_error()),
+    }
+}
+
+/// Returns `true` if `attrs` contains a bare marker attribute named `name`, e.g. `#[redis_hash]`.
+fn has_marker_attr(attrs: &[Attribute], name: &str) -> bool {
+    attrs
+        .iter()
+        .any(|a| a.path.segments.len() == 1 && a.path.segments[0].ident == name)
+}
+
+/// Returns the named fields of a struct, or a `compile_error!` token stream for anything else
+/// (enums, tuple/unit structs).
+fn named_fields<'a>(
+    ident: &Ident,
+    data: &'a Data,
+) -> std::result::Result<Vec<&'a Field>, TokenStream2> {
+    match data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(named) => Ok(named.named.iter().collect()),
+            _ => Err(Error::new_spanned(
+                ident,
+                "#[redis_hash] only supports structs with named fields",
+            )
+            .to_compile_error()),
+        },
+        _ => Err(Error::new_spanned(
+            ident,
+            "#[redis_hash] only supports structs with named fields",
+        )
+        .to_compile_error()),
+    }
+}
+
+/// Returns the fields of a struct in declaration order, or a `compile_error!` token stream for
+/// anything else (enums).
+fn struct_fields<'a>(ident: &Ident, data: &'a Data) -> std::result::Result<&'a Fields, TokenStream2> {
+    match data {
+        Data::Struct(s) => Ok(&s.fields),
+        _ => Err(Error::new_spanned(ident, "#[redis_native] only supports structs").to_compile_error()),
+    }
+}
+
+/// If `ty` is `Option<T>`, returns `Some(&T)`, otherwise `None`.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(t) => Some(t),
+        _ => None,
+    })
+}
+
 /// Derive macro for the redis crate's [`FromRedisValue`](../redis/trait.FromRedisValue.html) trait to allow parsing Redis responses to this type.
 ///
 /// *NOTE: This trait requires serde's [`Deserialize`](../serde/trait.Deserialize.html) to also be derived (or implemented).*
@@ -84,16 +167,68 @@ fn get_serializer(attrs: Vec<Attribute>, default: &str) -> TokenStream2 {
 /// struct Test {
 /// ```
 ///
+/// If `#[redis_hash]` is present on the struct, the generated impl instead expects a
+/// `redis::Value::Bulk` of alternating field-name/value pairs (as returned by `HGETALL`) and
+/// routes each value to the matching named field by name, deserializing it with the configured
+/// serializer. `Option<T>` fields default to `None` when their key is absent, unknown keys are
+/// ignored, and a missing non-`Option` field produces the usual `TypeError`. See [ToRedisArgs]
+/// for the paired `#[redis_hash]` writer. As with the writer, individual fields may carry their
+/// own `#[redis_serializer(...)]` to override the struct-level (or default) serializer.
+///
+/// ```rust,no_run
+/// use redis_macros_derive_bincode::FromRedisValue;
+/// use serde::Deserialize;
+///
+/// #[derive(FromRedisValue, Deserialize, Debug)]
+/// #[redis_hash]
+/// struct User {
+///     name: String,
+///     age: i64,
+/// }
+/// ```
+///
+/// ```rust,no_run
+/// use redis_macros_derive_bincode::FromRedisValue;
+/// use serde::Deserialize;
+///
+/// #[derive(FromRedisValue, Deserialize, Debug)]
+/// #[redis_hash]
+/// struct Event {
+///     #[redis_serializer(my_serializer)]
+///     timestamp: String,
+///     payload: Vec<u8>,
+/// }
+/// ```
+///
+/// If `#[redis_native]` is present instead, the generated impl walks the native RESP value
+/// structurally rather than decoding it with a serializer: a single-field tuple struct or
+/// newtype is parsed directly from the whole `redis::Value` (so `Int`, `Data`, `Status` and
+/// `Nil` work out of the box via their own [`FromRedisValue`] impls), while a struct with
+/// multiple fields expects a `redis::Value::Bulk` whose items are consumed positionally, one
+/// per field, recursing into any nested type's own `FromRedisValue` impl. Trailing `Option`
+/// fields may be omitted from the `Bulk`; any other arity mismatch produces a `TypeError`.
+///
+/// ```rust,no_run
+/// use redis_macros_derive_bincode::FromRedisValue;
+///
+/// #[derive(FromRedisValue, Debug)]
+/// #[redis_native]
+/// struct Coordinates(f64, f64);
+/// ```
+///
 /// For more information see the isomorphic pair of this trait: [ToRedisArgs].
-#[proc_macro_derive(FromRedisValue, attributes(redis_serializer))]
+#[proc_macro_derive(FromRedisValue, attributes(redis_serializer, redis_hash, redis_native))]
 pub fn from_redis_value_macro(input: TokenStream) -> TokenStream {
     let DeriveInput {
         ident,
         attrs,
         generics,
+        data,
         ..
     } = parse_macro_input!(input as DeriveInput);
-    let serializer = get_serializer(attrs, "bincode");
+    let serializer = get_serializer(&attrs, "bincode");
+    let is_hash = has_marker_attr(&attrs, "redis_hash");
+    let is_native = has_marker_attr(&attrs, "redis_native");
     let ident_str = format!("{}", ident);
     let serializer_str = format!("{}", serializer);
 
@@ -104,7 +239,11 @@ pub fn from_redis_value_macro(input: TokenStream) -> TokenStream {
         .iter()
         .any(|g| matches!(g, GenericParam::Type(_)));
 
-    let where_with_serialize = if let Some(w) = where_clause {
+    // In `#[redis_hash]`/`#[redis_native]` mode `Self` is built up field by field rather than
+    // deserialized as a whole, so the blanket `DeserializeOwned` bound below isn't needed there.
+    let where_with_serialize = if is_hash || is_native {
+        quote! { #where_clause }
+    } else if let Some(w) = where_clause {
         quote! { #w, #ident #ty_generics : serde::de::DeserializeOwned }
     } else if has_types {
         quote! { where #ident #ty_generics : serde::de::DeserializeOwned }
@@ -120,25 +259,168 @@ pub fn from_redis_value_macro(input: TokenStream) -> TokenStream {
         )))
     };
 
-    quote! {
-        impl #impl_generics redis::FromRedisValue for #ident #ty_generics #where_with_serialize {
-            fn from_redis_value(v: &redis::Value) -> redis::RedisResult<Self> {
+    let incompatible_type_error = quote! {
+        Err(redis::RedisError::from((
+            redis::ErrorKind::TypeError,
+            "Response was of incompatible type",
+            format!("Response type was not deserializable to {}. (response was {:?})", #ident_str, v)
+        )))
+    };
+
+    let body = if is_hash {
+        let fields = match named_fields(&ident, &data) {
+            Ok(fields) => fields,
+            Err(e) => return e.into(),
+        };
+        let field_inits = fields.iter().map(|field| {
+            let field_ident = field.ident.as_ref().unwrap();
+            let field_name = field_ident.to_string();
+            let field_serializer = get_field_serializer(&field.attrs, &serializer);
+            if let Some(_inner) = option_inner_type(&field.ty) {
+                quote! {
+                    #field_ident: match fields.get(#field_name) {
+                        Some(redis::Value::Data(bytes)) => #field_serializer::deserialize(bytes).ok(),
+                        _ => None,
+                    }
+                }
+            } else {
+                let missing_field_error = quote! {
+                    Err(redis::RedisError::from((
+                        redis::ErrorKind::TypeError,
+                        "Response was of incompatible type",
+                        format!("Missing field \"{}\" for {}. (response was {:?})", #field_name, #ident_str, v)
+                    )))
+                };
+                quote! {
+                    #field_ident: match fields.get(#field_name) {
+                        Some(redis::Value::Data(bytes)) => match #field_serializer::deserialize(bytes) {
+                            Ok(value) => value,
+                            Err(_) => return #failed_parse_error,
+                        },
+                        Some(_) => return #incompatible_type_error,
+                        None => return #missing_field_error,
+                    }
+                }
+            }
+        });
+
+        quote! {
+            match *v {
+                redis::Value::Bulk(ref items) => {
+                    let mut fields: std::collections::HashMap<String, &redis::Value> = std::collections::HashMap::new();
+                    let mut pairs = items.iter();
+                    while let (Some(key), Some(value)) = (pairs.next(), pairs.next()) {
+                        if let Ok(key) = String::from_redis_value(key) {
+                            fields.insert(key, value);
+                        }
+                    }
+                    Ok(#ident {
+                        #(#field_inits),*
+                    })
+                },
+                _ => #incompatible_type_error,
+            }
+        }
+    } else if is_native {
+        let fields = match struct_fields(&ident, &data) {
+            Ok(fields) => fields,
+            Err(e) => return e.into(),
+        };
+        let (field_types, is_named): (Vec<&Type>, bool) = match fields {
+            Fields::Named(named) => (named.named.iter().map(|f| &f.ty).collect(), true),
+            Fields::Unnamed(unnamed) => (unnamed.unnamed.iter().map(|f| &f.ty).collect(), false),
+            Fields::Unit => (Vec::new(), false),
+        };
+        let is_unit = matches!(fields, Fields::Unit);
+        let field_idents: Vec<Option<&syn::Ident>> = match fields {
+            Fields::Named(named) => named.named.iter().map(|f| f.ident.as_ref()).collect(),
+            _ => field_types.iter().map(|_| None).collect(),
+        };
+        let field_count = field_types.len();
+
+        if field_count == 1 {
+            let ty = field_types[0];
+            let value = quote! { <#ty as redis::FromRedisValue>::from_redis_value(v)? };
+            let construct = if is_named {
+                let name = field_idents[0].unwrap();
+                quote! { #ident { #name: #value } }
+            } else {
+                quote! { #ident(#value) }
+            };
+            quote! { Ok(#construct) }
+        } else {
+            let trailing_optional = field_types
+                .iter()
+                .rev()
+                .take_while(|ty| option_inner_type(ty).is_some())
+                .count();
+            let required_count = field_count - trailing_optional;
+
+            let field_inits = field_types.iter().enumerate().map(|(i, ty)| {
+                let value = if let Some(inner) = option_inner_type(ty) {
+                    quote! {
+                        match items.get(#i) {
+                            Some(redis::Value::Nil) | None => None,
+                            Some(item) => <#inner as redis::FromRedisValue>::from_redis_value(item).ok(),
+                        }
+                    }
+                } else {
+                    quote! {
+                        match items.get(#i) {
+                            Some(item) => <#ty as redis::FromRedisValue>::from_redis_value(item)?,
+                            None => return #incompatible_type_error,
+                        }
+                    }
+                };
+                match field_idents[i] {
+                    Some(name) => quote! { #name: #value },
+                    None => value,
+                }
+            });
+
+            let construct = if is_named {
+                quote! { #ident { #(#field_inits),* } }
+            } else if is_unit {
+                // A bare unit struct has no parens to call, unlike a zero-field tuple struct.
+                quote! { #ident }
+            } else {
+                quote! { #ident(#(#field_inits),*) }
+            };
+
+            quote! {
                 match *v {
-                    redis::Value::Data(ref bytes) => {
-                        if let Ok(s) = #serializer::deserialize(bytes) {
-                            Ok(s)
+                    redis::Value::Bulk(ref items) => {
+                        if items.len() > #field_count || items.len() < #required_count {
+                            #incompatible_type_error
                         } else {
-                            #failed_parse_error
+                            Ok(#construct)
                         }
                     },
-                    _ => Err(redis::RedisError::from((
-                        redis::ErrorKind::TypeError,
-                        "Response was of incompatible type",
-                        format!("Response type was not deserializable to {}. (response was {:?})", #ident_str, v)
-                    ))),
+                    _ => #incompatible_type_error,
                 }
             }
         }
+    } else {
+        quote! {
+            match *v {
+                redis::Value::Data(ref bytes) => {
+                    if let Ok(s) = #serializer::deserialize(bytes) {
+                        Ok(s)
+                    } else {
+                        #failed_parse_error
+                    }
+                },
+                _ => #incompatible_type_error,
+            }
+        }
+    };
+
+    quote! {
+        impl #impl_generics redis::FromRedisValue for #ident #ty_generics #where_with_serialize {
+            fn from_redis_value(v: &redis::Value) -> redis::RedisResult<Self> {
+                #body
+            }
+        }
     }
     .into()
 }
@@ -147,7 +429,9 @@ pub fn from_redis_value_macro(input: TokenStream) -> TokenStream {
 ///
 /// *NOTE: This trait requires serde's [`Serialize`](../serde/trait.Serialize.html) to also be derived (or implemented).*
 ///
-/// ***WARNING: This trait panics if the underlying serialization fails.***
+/// *NOTE: If the underlying serialization fails, `write_redis_args` silently writes no args
+/// rather than panicking. Call the generated `try_write_redis_args` method instead if you need
+/// to detect that failure.*
 ///
 /// Simply use the `#[derive(ToRedisArgs, Serialize)]` before any structs (or serializable elements).
 /// This allows to pass this type to Redis commands like SET. The type will be serialized into binary automatically while saving to Redis.
@@ -191,16 +475,95 @@ pub fn from_redis_value_macro(input: TokenStream) -> TokenStream {
 /// struct Test{
 /// ```
 ///
+/// If `#[redis_hash]` is present on the struct, the generated impl instead writes, for each named
+/// field, the field name followed by the field's serialized value, so the result works directly
+/// with `HSET key f1 v1 f2 v2 ...` instead of as a single opaque blob. See [FromRedisValue] for
+/// the paired `#[redis_hash]` reader.
+///
+/// ```rust,no_run
+/// use redis_macros_derive_bincode::ToRedisArgs;
+/// use serde::Serialize;
+///
+/// #[derive(ToRedisArgs, Serialize, Debug)]
+/// #[redis_hash]
+/// struct User {
+///     name: String,
+///     age: i64,
+/// }
+/// ```
+///
+/// In `#[redis_hash]` mode, individual fields can also carry their own `#[redis_serializer(...)]`
+/// to override the struct-level (or default) serializer for just that field, e.g. to keep most
+/// fields in bincode while storing one as a human-readable format for other clients.
+///
+/// ```rust,no_run
+/// use redis_macros_derive_bincode::ToRedisArgs;
+/// use serde::Serialize;
+///
+/// #[derive(ToRedisArgs, Serialize, Debug)]
+/// #[redis_hash]
+/// struct Event {
+///     #[redis_serializer(my_serializer)]
+///     timestamp: String,
+///     payload: Vec<u8>,
+/// }
+/// ```
+///
+/// If `#[redis_expiry(...)]` is present, using one of the [`redis::Expiry`] variants
+/// (`EX(secs)`, `PX(ms)`, `EXAT(secs)`, `PXAT(ms)`, `PERSIST`), two inherent methods are
+/// generated alongside the trait impl: `set_with_expiry` serializes `self` and issues
+/// `SET key <bytes>` with the configured expiry baked in, while `set_with_expiry_as` takes an
+/// `redis::Expiry` argument so callers can override it per call. `#[redis_expiry(...)]` cannot be
+/// combined with `#[redis_hash]`, since the expiry helpers write a single `SET` blob rather than
+/// the per-field `HSET` encoding `#[redis_hash]` uses; doing so is a compile error.
+///
+/// ```rust,no_run
+/// use redis_macros_derive_bincode::ToRedisArgs;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize, ToRedisArgs)]
+/// #[redis_expiry(EX(60))]
+/// struct Session {
+///     token: String,
+/// }
+///
+/// # fn main() -> redis::RedisResult<()> {
+/// # let client = redis::Client::open("redis://localhost:6379/")?;
+/// # let mut con = client.get_connection()?;
+/// let session = Session { token: "abc".to_string() };
+/// session.set_with_expiry(&mut con, "session:abc")?;
+/// # Ok(())
+/// # }
+/// ```
+///
 /// For more information see the isomorphic pair of this trait: [FromRedisValue].
-#[proc_macro_derive(ToRedisArgs, attributes(redis_serializer))]
+#[proc_macro_derive(ToRedisArgs, attributes(redis_serializer, redis_hash, redis_expiry))]
 pub fn to_redis_args_macro(input: TokenStream) -> TokenStream {
     let DeriveInput {
         ident,
         attrs,
         generics,
+        data,
         ..
     } = parse_macro_input!(input as DeriveInput);
-    let serializer = get_serializer(attrs, "bincode");
+    let serializer = get_serializer(&attrs, "bincode");
+    let is_hash = has_marker_attr(&attrs, "redis_hash");
+    let default_expiry = match get_attr_args(&attrs, "redis_expiry") {
+        Ok(default_expiry) => default_expiry,
+        Err(e) => return e.into(),
+    };
+    if is_hash && default_expiry.is_some() {
+        return Error::new_spanned(
+            &ident,
+            "#[redis_expiry(...)] is not supported together with #[redis_hash]: the expiry \
+             helpers write a single `SET key <bytes>` blob, which is incompatible with \
+             #[redis_hash]'s per-field `HSET` encoding",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let ident_str = format!("{}", ident);
+    let serializer_str = format!("{}", serializer);
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
@@ -217,16 +580,110 @@ pub fn to_redis_args_macro(input: TokenStream) -> TokenStream {
         quote! {}
     };
 
+    let body = if is_hash {
+        let fields = match named_fields(&ident, &data) {
+            Ok(fields) => fields,
+            Err(e) => return e.into(),
+        };
+        let field_serializes = fields.iter().map(|field| {
+            let field_ident = field.ident.as_ref().unwrap();
+            let field_name = field_ident.to_string();
+            let field_serializer = get_field_serializer(&field.attrs, &serializer);
+            quote! {
+                match #field_serializer::serialize(&self.#field_ident) {
+                    Ok(buf) => args.push((#field_name, buf)),
+                    Err(_) => return,
+                }
+            }
+        });
+        quote! {
+            // Serialize every field before writing any of them, so a single un-encodable
+            // field leaves no partial command behind instead of panicking.
+            let mut args: Vec<(&str, Vec<u8>)> = Vec::new();
+            #(#field_serializes)*
+            for (name, buf) in args {
+                out.write_arg(name.as_bytes());
+                out.write_arg(&buf);
+            }
+        }
+    } else {
+        quote! {
+            if let Ok(buf) = self.try_write_redis_args() {
+                out.write_arg(&buf);
+            }
+        }
+    };
+
+    let try_write_impl = if is_hash {
+        quote! {}
+    } else {
+        quote! {
+            impl #impl_generics #ident #ty_generics #where_with_serialize {
+                /// Serializes `self` with the configured serializer, returning the encode error as a
+                /// [`redis::RedisError`] instead of silently writing no args, which is what happens
+                /// when this type is passed to a Redis command and serialization fails.
+                pub fn try_write_redis_args(&self) -> redis::RedisResult<Vec<u8>> {
+                    #serializer::serialize(self).map_err(|e| {
+                        redis::RedisError::from((
+                            redis::ErrorKind::TypeError,
+                            "Could not serialize value",
+                            format!("Failed to serialize {} with {}: {}", #ident_str, #serializer_str, e)
+                        ))
+                    })
+                }
+            }
+        }
+    };
+
+    let expiry_impl = default_expiry.map(|default_expiry| {
+        quote! {
+            impl #impl_generics #ident #ty_generics #where_with_serialize {
+                /// Serializes `self` with the configured serializer and issues `SET key <bytes>`
+                /// with the expiry configured via `#[redis_expiry(...)]`.
+                pub fn set_with_expiry<C: redis::ConnectionLike>(
+                    &self,
+                    con: &mut C,
+                    key: &str,
+                ) -> redis::RedisResult<()> {
+                    self.set_with_expiry_as(con, key, redis::Expiry::#default_expiry)
+                }
+
+                /// Like [`Self::set_with_expiry`], but lets the caller choose the expiry at call time.
+                pub fn set_with_expiry_as<C: redis::ConnectionLike>(
+                    &self,
+                    con: &mut C,
+                    key: &str,
+                    expiry: redis::Expiry,
+                ) -> redis::RedisResult<()> {
+                    let buf = self.try_write_redis_args()?;
+                    let mut cmd = redis::cmd("SET");
+                    cmd.arg(key).arg(buf);
+                    match expiry {
+                        redis::Expiry::EX(secs) => { cmd.arg("EX").arg(secs); },
+                        redis::Expiry::PX(ms) => { cmd.arg("PX").arg(ms); },
+                        redis::Expiry::EXAT(timestamp_secs) => { cmd.arg("EXAT").arg(timestamp_secs); },
+                        redis::Expiry::PXAT(timestamp_ms) => { cmd.arg("PXAT").arg(timestamp_ms); },
+                        redis::Expiry::PERSIST => { cmd.arg("PERSIST"); },
+                    };
+                    cmd.query(con)
+                }
+            }
+        }
+    });
+
     quote! {
         impl #impl_generics redis::ToRedisArgs for #ident #ty_generics #where_with_serialize {
             fn write_redis_args<W>(&self, out: &mut W)
             where
                 W: ?Sized + redis::RedisWrite,
             {
-                let buf = #serializer::serialize(&self).unwrap();
-                return out.write_arg(&buf);
+                #body
             }
         }
+
+        #try_write_impl
+
+        #expiry_impl
     }
     .into()
 }
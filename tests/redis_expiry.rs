@@ -0,0 +1,77 @@
+use redis::{ConnectionLike, RedisResult, Value};
+use redis_macros_derive_bincode::ToRedisArgs;
+use serde::Serialize;
+
+#[derive(Serialize, ToRedisArgs)]
+#[redis_expiry(EX(60))]
+struct Session {
+    token: String,
+}
+
+#[derive(Default)]
+struct RecordingConnection {
+    packed_commands: Vec<Vec<u8>>,
+}
+
+impl ConnectionLike for RecordingConnection {
+    fn req_packed_command(&mut self, cmd: &[u8]) -> RedisResult<Value> {
+        self.packed_commands.push(cmd.to_vec());
+        Ok(Value::Okay)
+    }
+
+    fn req_packed_commands(
+        &mut self,
+        _cmd: &[u8],
+        _offset: usize,
+        _count: usize,
+    ) -> RedisResult<Vec<Value>> {
+        Ok(vec![])
+    }
+
+    fn get_db(&self) -> i64 {
+        0
+    }
+
+    fn check_connection(&mut self) -> bool {
+        true
+    }
+
+    fn is_open(&self) -> bool {
+        true
+    }
+}
+
+fn packed_command_contains(bytes: &[u8], needle: &str) -> bool {
+    String::from_utf8_lossy(bytes).contains(needle)
+}
+
+#[test]
+fn set_with_expiry_uses_the_default_expiry() {
+    let session = Session {
+        token: "abc".to_string(),
+    };
+    let mut con = RecordingConnection::default();
+    session.set_with_expiry(&mut con, "session:abc").unwrap();
+
+    assert_eq!(con.packed_commands.len(), 1);
+    let cmd = &con.packed_commands[0];
+    assert!(packed_command_contains(cmd, "SET"));
+    assert!(packed_command_contains(cmd, "session:abc"));
+    assert!(packed_command_contains(cmd, "EX"));
+    assert!(packed_command_contains(cmd, "60"));
+}
+
+#[test]
+fn set_with_expiry_as_overrides_the_expiry_per_call() {
+    let session = Session {
+        token: "abc".to_string(),
+    };
+    let mut con = RecordingConnection::default();
+    session
+        .set_with_expiry_as(&mut con, "session:abc", redis::Expiry::PERSIST)
+        .unwrap();
+
+    let cmd = &con.packed_commands[0];
+    assert!(packed_command_contains(cmd, "PERSIST"));
+    assert!(!packed_command_contains(cmd, "EX"));
+}
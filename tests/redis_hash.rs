@@ -0,0 +1,65 @@
+use redis::{FromRedisValue, ToRedisArgs, Value};
+use redis_macros_derive_bincode::{FromRedisValue, ToRedisArgs};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, FromRedisValue, ToRedisArgs, Debug, PartialEq)]
+#[redis_hash]
+struct User {
+    name: String,
+    age: i64,
+}
+
+fn written_args(user: &User) -> Vec<Vec<u8>> {
+    let mut args = Vec::new();
+    user.write_redis_args(&mut args);
+    args
+}
+
+#[test]
+fn writes_field_name_value_pairs() {
+    let user = User {
+        name: "Ada".to_string(),
+        age: 36,
+    };
+    let args = written_args(&user);
+    assert_eq!(args.len(), 4);
+    assert_eq!(args[0], b"name".to_vec());
+    assert_eq!(bincode::deserialize::<String>(&args[1]).unwrap(), "Ada");
+    assert_eq!(args[2], b"age".to_vec());
+    assert_eq!(bincode::deserialize::<i64>(&args[3]).unwrap(), 36);
+}
+
+#[test]
+fn round_trips_through_hgetall_style_bulk() {
+    let user = User {
+        name: "Grace".to_string(),
+        age: 49,
+    };
+    let args = written_args(&user);
+    let bulk = Value::Bulk(args.into_iter().map(Value::Data).collect());
+    let parsed = User::from_redis_value(&bulk).unwrap();
+    assert_eq!(parsed, user);
+}
+
+#[test]
+fn missing_optional_field_defaults_to_none() {
+    #[derive(Serialize, Deserialize, FromRedisValue, ToRedisArgs, Debug, PartialEq)]
+    #[redis_hash]
+    struct Profile {
+        name: String,
+        nickname: Option<String>,
+    }
+
+    let name_key = Value::Data(b"name".to_vec());
+    let name_val = Value::Data(bincode::serialize("Lin").unwrap());
+    let bulk = Value::Bulk(vec![name_key, name_val]);
+
+    let parsed = Profile::from_redis_value(&bulk).unwrap();
+    assert_eq!(
+        parsed,
+        Profile {
+            name: "Lin".to_string(),
+            nickname: None,
+        }
+    );
+}
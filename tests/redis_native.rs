@@ -0,0 +1,72 @@
+use redis::{FromRedisValue, ToRedisArgs, Value};
+use redis_macros_derive_bincode::FromRedisValue;
+
+#[derive(FromRedisValue, Debug, PartialEq)]
+#[redis_native]
+struct Coordinates(f64, f64);
+
+#[derive(FromRedisValue, Debug, PartialEq)]
+#[redis_native]
+struct Token(String);
+
+#[derive(FromRedisValue, Debug, PartialEq)]
+#[redis_native]
+struct Profile {
+    name: String,
+    nickname: Option<String>,
+}
+
+#[derive(FromRedisValue, Debug, PartialEq)]
+#[redis_native]
+struct Marker;
+
+fn data(value: impl ToRedisArgs) -> Value {
+    Value::Data(value.to_redis_args().into_iter().next().unwrap())
+}
+
+#[test]
+fn multi_field_struct_round_trips_through_bulk() {
+    let bulk = Value::Bulk(vec![data(12.5_f64), data(-3.0_f64)]);
+    assert_eq!(
+        Coordinates::from_redis_value(&bulk).unwrap(),
+        Coordinates(12.5, -3.0)
+    );
+}
+
+#[test]
+fn single_field_newtype_parses_from_whole_value() {
+    let value = data("abc123".to_string());
+    assert_eq!(
+        Token::from_redis_value(&value).unwrap(),
+        Token("abc123".to_string())
+    );
+}
+
+#[test]
+fn trailing_option_field_may_be_omitted_from_bulk() {
+    let bulk = Value::Bulk(vec![data("Ada".to_string())]);
+    let parsed = Profile::from_redis_value(&bulk).unwrap();
+    assert_eq!(
+        parsed,
+        Profile {
+            name: "Ada".to_string(),
+            nickname: None,
+        }
+    );
+}
+
+#[test]
+fn unit_struct_round_trips_through_empty_bulk() {
+    let bulk = Value::Bulk(vec![]);
+    assert_eq!(Marker::from_redis_value(&bulk).unwrap(), Marker);
+}
+
+#[test]
+fn arity_mismatch_is_a_type_error() {
+    let bulk = Value::Bulk(vec![
+        data("Ada".to_string()),
+        data("x".to_string()),
+        data("y".to_string()),
+    ]);
+    assert!(Profile::from_redis_value(&bulk).is_err());
+}
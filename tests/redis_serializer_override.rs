@@ -0,0 +1,48 @@
+use redis::{FromRedisValue, ToRedisArgs, Value};
+use redis_macros_derive_bincode::{FromRedisValue, ToRedisArgs};
+use serde::{Deserialize, Serialize};
+
+mod json_serializer {
+    use serde::{de::DeserializeOwned, Serialize};
+
+    pub fn serialize<T: Serialize>(value: &T) -> serde_json::Result<Vec<u8>> {
+        serde_json::to_vec(value)
+    }
+
+    pub fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> serde_json::Result<T> {
+        serde_json::from_slice(bytes)
+    }
+}
+
+#[derive(Serialize, Deserialize, FromRedisValue, ToRedisArgs, Debug, PartialEq)]
+#[redis_hash]
+struct Event {
+    #[redis_serializer(json_serializer)]
+    timestamp: String,
+    payload: Vec<u8>,
+}
+
+#[test]
+fn field_level_serializer_overrides_struct_default() {
+    let event = Event {
+        timestamp: "2026-07-25T00:00:00Z".to_string(),
+        payload: vec![1, 2, 3],
+    };
+
+    let mut args: Vec<Vec<u8>> = Vec::new();
+    event.write_redis_args(&mut args);
+
+    // timestamp was encoded with json_serializer, not bincode.
+    assert_eq!(args[0], b"timestamp".to_vec());
+    assert_eq!(
+        serde_json::from_slice::<String>(&args[1]).unwrap(),
+        event.timestamp
+    );
+
+    // payload still used the struct-level (default bincode) serializer.
+    assert_eq!(args[2], b"payload".to_vec());
+    assert_eq!(bincode::deserialize::<Vec<u8>>(&args[3]).unwrap(), event.payload);
+
+    let bulk = Value::Bulk(args.into_iter().map(Value::Data).collect());
+    assert_eq!(Event::from_redis_value(&bulk).unwrap(), event);
+}
@@ -0,0 +1,59 @@
+use redis::{ErrorKind, ToRedisArgs};
+use redis_macros_derive_bincode::ToRedisArgs as DeriveToRedisArgs;
+use serde::Serialize;
+
+#[derive(Serialize, DeriveToRedisArgs)]
+struct Test {
+    first_field: String,
+    second_field: i64,
+}
+
+#[test]
+fn returns_the_serialized_bytes_on_success() {
+    let test = Test {
+        first_field: "Hello".to_string(),
+        second_field: 42,
+    };
+    let buf = test.try_write_redis_args().unwrap();
+    assert_eq!(bincode::deserialize::<Test2>(&buf).unwrap(), Test2 {
+        first_field: "Hello".to_string(),
+        second_field: 42,
+    });
+}
+
+#[derive(serde::Deserialize, PartialEq, Debug)]
+struct Test2 {
+    first_field: String,
+    second_field: i64,
+}
+
+mod failing_serializer {
+    pub fn serialize<T>(_value: &T) -> Result<Vec<u8>, &'static str> {
+        Err("nope")
+    }
+}
+
+#[derive(Serialize, DeriveToRedisArgs)]
+#[redis_serializer(failing_serializer)]
+struct Unserializable {
+    value: String,
+}
+
+#[test]
+fn surfaces_the_serialize_error_as_a_type_error_instead_of_writing_nothing() {
+    let value = Unserializable {
+        value: "x".to_string(),
+    };
+    let err = value.try_write_redis_args().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TypeError);
+}
+
+#[test]
+fn write_redis_args_silently_writes_nothing_on_the_same_failure() {
+    let value = Unserializable {
+        value: "x".to_string(),
+    };
+    let mut args: Vec<Vec<u8>> = Vec::new();
+    value.write_redis_args(&mut args);
+    assert!(args.is_empty());
+}